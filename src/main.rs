@@ -1,29 +1,151 @@
 use bevy::{
+    asset::LoadState,
+    core_pipeline::Skybox,
     dev_tools::fps_overlay::{FpsOverlayConfig, FpsOverlayPlugin},
     input::mouse::MouseMotion,
     prelude::{App, *},
+    render::render_resource::{TextureViewDescriptor, TextureViewDimension},
     text::{FontSmoothing, LineHeight},
     window::{CursorGrabMode, PresentMode, PrimaryWindow},
 };
 use bevy_egui::{EguiContextPass, EguiContexts, EguiPlugin, egui};
-use bevy_rapier3d::prelude::*;
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
-
-static VSYNC: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
-static BALL_COUNTER: Lazy<Mutex<u16>> = Lazy::new(|| Mutex::new(1));
-static MOUSE_SENSITIVITY: Lazy<Mutex<f32>> = Lazy::new(|| Mutex::new(0.5));
-static GROUND_SIZE: Lazy<Mutex<Vec3>> = Lazy::new(|| {
-    Mutex::new(Vec3 {
-        x: 20.0,
-        y: 1.0,
-        z: 15.0,
-    })
-});
+use bevy_ggrs::{
+    ggrs::{self, PlayerType, SessionBuilder, UdpNonBlockingSocket},
+    AddRollbackCommandExtension, GgrsApp, GgrsPlugin, GgrsSchedule, LocalInputs, LocalPlayers,
+    PlayerInputs, ReadInputs, Session,
+};
+use bytemuck::{Pod, Zeroable};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::net::SocketAddr;
+
+mod physics;
+use physics as phys;
+
+// Both peers must agree on this.
+const FPS: usize = 60;
+
+const SPRINT_MULTIPLIER: f32 = 2.0;
+
+const SETTINGS_PATH: &str = "settings.ron";
+
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq)]
+struct Settings {
+    vsync: bool,
+    ball_counter: u16,
+    mouse_sensitivity: f32,
+    move_speed: f32,
+    ground_size: Vec3,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            vsync: false,
+            ball_counter: 1,
+            mouse_sensitivity: 0.5,
+            move_speed: 5.0,
+            ground_size: Vec3 {
+                x: 20.0,
+                y: 1.0,
+                z: 15.0,
+            },
+        }
+    }
+}
+
+// Falls back to defaults if the file is missing or fails to parse.
+fn load_settings() -> Settings {
+    File::open(SETTINGS_PATH)
+        .ok()
+        .and_then(|file| ron::de::from_reader(BufReader::new(file)).ok())
+        .unwrap_or_default()
+}
+
+// Best-effort: a save failure just logs.
+fn save_settings(settings: &Settings) {
+    let ron = match ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default()) {
+        Ok(ron) => ron,
+        Err(err) => {
+            println!("Failed to serialize settings: {err}");
+            return;
+        }
+    };
+
+    match File::create(SETTINGS_PATH).and_then(|mut file| file.write_all(ron.as_bytes())) {
+        Ok(()) => {}
+        Err(err) => println!("Failed to save settings to {SETTINGS_PATH}: {err}"),
+    }
+}
+
+fn load_settings_system(mut commands: Commands) {
+    let settings = load_settings();
+    commands.insert_resource(PreviousSettings(settings.clone()));
+    commands.insert_resource(settings);
+}
+
+// `game_ui` takes `ResMut<Settings>` and writes through it every frame the settings
+// windows are open, which marks the resource changed via `DerefMut` whether or not a
+// value actually moved. Compare against the last-saved value instead of trusting
+// `is_changed()`, so `save_settings` only runs when something really changed.
+#[derive(Resource)]
+struct PreviousSettings(Settings);
+
+fn persist_settings(settings: Res<Settings>, mut previous: ResMut<PreviousSettings>) {
+    if *settings != previous.0 {
+        save_settings(&settings);
+        previous.0 = settings.clone();
+    }
+}
+
+mod input_bits {
+    pub const UP: u8 = 1 << 0;
+    pub const DOWN: u8 = 1 << 1;
+    pub const LEFT: u8 = 1 << 2;
+    pub const RIGHT: u8 = 1 << 3;
+    pub const JUMP: u8 = 1 << 4;
+    pub const SPRINT: u8 = 1 << 5;
+}
+
+// Packed small so `ggrs` can snapshot it every frame (requires `Pod`/`Zeroable`).
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Pod, Zeroable)]
+struct BoxInput {
+    buttons: u8,
+    ball_target: u8,
+}
+
+// State is unused; Rapier's own snapshotting handles rollback state.
+struct NetworkConfig;
+
+impl ggrs::Config for NetworkConfig {
+    type Input = BoxInput;
+    type State = u8;
+    type Address = SocketAddr;
+}
+
+// Each is a vertical strip of 6 square faces — the layout
+// `reinterpret_stacked_2d_as_array` expects.
+const SKYBOXES: &[&str] = &[
+    "textures/skybox_day.png",
+    "textures/skybox_sunset.png",
+    "textures/skybox_night.png",
+];
+
+#[derive(Resource)]
+struct SkyboxState {
+    handle: Handle<Image>,
+    index: usize,
+    reinterpreted: bool,
+}
 
 struct OverlayColor;
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct SmallBall;
 
 #[derive(Component)]
@@ -35,6 +157,16 @@ struct PreviousGroundSize(Vec3);
 #[derive(Component)]
 struct Ground;
 
+// Lets `stay_inside_big_ball_system` read the live radius/position instead of
+// duplicating the numbers `setup` spawned it with.
+#[derive(Component)]
+struct BigBall;
+
+// Derived by `sync_ball_target` from handle 0's input each tick, not read straight from
+// `Settings` — `Settings` isn't synced across peers.
+#[derive(Resource, Default)]
+struct BallTarget(u16);
+
 #[allow(dead_code)]
 impl OverlayColor {
     const RED: Color = Color::srgb(1.0, 0.0, 0.0);
@@ -43,12 +175,6 @@ impl OverlayColor {
 
 fn main() {
     let mut app = App::new();
-    
-    #[cfg(debug_assertions)]
-    {
-        app.add_plugins(RapierDebugRenderPlugin::default());
-        println!("Debug Mode: Rapier Debug Render Plugin Loaded!!!");
-    }
 
     app.add_plugins((
             DefaultPlugins,
@@ -66,59 +192,262 @@ fn main() {
                 },
             },
         ))
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugins(GgrsPlugin::<NetworkConfig>::default())
         .add_plugins(EguiPlugin {
             enable_multipass_for_primary_context: true,
         })
         .insert_resource(PreviousGroundSize(Vec3::ZERO))
+        .init_resource::<BallTarget>()
+        .add_systems(Startup, load_settings_system)
+        .add_systems(Startup, setup_skybox)
+        .add_systems(Update, apply_skybox)
+        .set_rollback_schedule_fps(FPS)
+        .rollback_component_with_clone::<Transform>()
+        .rollback_component_with_copy::<phys::Velocity>()
+        .rollback_component_with_clone::<SmallBall>()
+        .rollback_component_with_copy::<CameraController>()
         .add_systems(EguiContextPass, game_ui)
-        .add_systems(Startup, (setup, setup_camera, setup_light))
+        .add_systems(Startup, (setup, setup_camera, setup_light).after(load_settings_system))
+        .add_systems(Startup, start_local_session)
+        .add_systems(ReadInputs, read_local_inputs)
         .add_systems(Update, (
-            keybinds, 
-            game_setting, 
-            ground_change_detector, 
-            mouse_free_look, 
-            mouse_movement,
-            stay_inside_big_ball_system
-        ))
-        .run();
+            keybinds,
+            game_setting,
+            ground_change_detector,
+            mouse_free_look,
+            persist_settings,
+        ));
+
+    build_physics(&mut app);
+
+    app.run();
+
+    // start_local_session() keeps this demo playable (and `GgrsSchedule` actually
+    // advancing) without any networking; swap it for start_p2p_session() once the
+    // lobby/menu flow that picks opponents ships — both insert the same
+    // `Session<NetworkConfig>` resource GGRS reads every tick.
+}
+
+// Without a session resource, `GgrsSchedule` never advances a frame.
+fn start_local_session(mut commands: Commands) {
+    let session = SessionBuilder::<NetworkConfig>::new()
+        .with_num_players(1)
+        .start_synctest_session()
+        .expect("failed to start local sync-test session");
+    commands.insert_resource(Session::SyncTestSession(session));
+}
+
+// Drives Rapier's sync/step/writeback sets from `GgrsSchedule` so peers resimulate
+// identically on rollback.
+#[cfg(not(feature = "avian"))]
+fn build_physics(app: &mut App) {
+    use bevy_rapier3d::prelude::{PhysicsSet, RapierConfiguration, TimestepMode};
+
+    #[cfg(debug_assertions)]
+    {
+        app.add_plugins(phys::DebugRenderPlugin::default());
+        println!("Debug Mode: Rapier Debug Render Plugin Loaded!!!");
+    }
+
+    /// Pins Rapier to a fixed 60Hz timestep with a single substep so stepping is
+    /// deterministic across peers instead of tracking the renderer's variable delta.
+    fn configure_fixed_timestep(mut rapier_config: Query<&mut RapierConfiguration>) {
+        for mut config in rapier_config.iter_mut() {
+            config.timestep_mode = TimestepMode::Fixed {
+                dt: 1.0 / FPS as f32,
+                substeps: 1,
+            };
+        }
+    }
+
+    app
+        // Disable Rapier's own scheduling and drive its sync/step/writeback sets from
+        // `GgrsSchedule` instead, so both peers re-simulate identically on rollback.
+        .add_plugins(phys::physics_plugin())
+        .add_systems(Startup, configure_fixed_timestep.after(load_settings_system))
+        .configure_sets(
+            GgrsSchedule,
+            (PhysicsSet::SyncBackend, PhysicsSet::StepSimulation, PhysicsSet::Writeback).chain(),
+        )
+        .add_systems(
+            GgrsSchedule,
+            (
+                phys::RapierPhysicsPlugin::<phys::NoUserData>::get_systems(PhysicsSet::SyncBackend)
+                    .in_set(PhysicsSet::SyncBackend),
+                phys::RapierPhysicsPlugin::<phys::NoUserData>::get_systems(PhysicsSet::StepSimulation)
+                    .in_set(PhysicsSet::StepSimulation),
+                phys::RapierPhysicsPlugin::<phys::NoUserData>::get_systems(PhysicsSet::Writeback)
+                    .in_set(PhysicsSet::Writeback),
+            ),
+        )
+        // Spawn/despawn ahead of SyncBackend so a freshly spawned ball's collider is
+        // picked up by Rapier the same tick it appears, on both peers.
+        .add_systems(
+            GgrsSchedule,
+            (sync_ball_target, sync_ball_count).chain().before(PhysicsSet::SyncBackend),
+        )
+        .add_systems(
+            GgrsSchedule,
+            (apply_rollback_inputs, stay_inside_big_ball_system)
+                .chain()
+                .after(PhysicsSet::Writeback),
+        );
+}
+
+// Avian isn't GGRS-integrated yet, so this runs outside rollback. Unreachable for now:
+// see the NOTE atop src/physics.rs — there's no Cargo.toml in this tree to declare the
+// `avian` feature or the optional `avian3d` dependency it would pull in.
+#[cfg(feature = "avian")]
+fn build_physics(app: &mut App) {
+    #[cfg(debug_assertions)]
+    {
+        app.add_plugins(phys::DebugRenderPlugin::default());
+        println!("Debug Mode: Avian Physics Debug Render Plugin Loaded!!!");
+    }
+
+    app.add_plugins(phys::physics_plugin())
+        .add_systems(GgrsSchedule, (sync_ball_target, sync_ball_count).chain())
+        .add_systems(
+            Update,
+            (apply_rollback_inputs, stay_inside_big_ball_system).chain(),
+        );
+}
+
+// 2-frame input delay, 12-frame max prediction window.
+fn start_p2p_session(local_port: u16, remote_addr: SocketAddr) -> ggrs::P2PSession<NetworkConfig> {
+    let socket = UdpNonBlockingSocket::bind_to_port(local_port).expect("failed to bind UDP socket");
+
+    SessionBuilder::<NetworkConfig>::new()
+        .with_num_players(2)
+        .with_max_prediction_window(12)
+        .expect("prediction window out of range")
+        .with_input_delay(2)
+        .add_player(PlayerType::Local, 0)
+        .expect("failed to add local player")
+        .add_player(PlayerType::Remote(remote_addr), 1)
+        .expect("failed to add remote player")
+        .start_p2p_session(socket)
+        .expect("failed to start p2p session")
+}
+
+fn read_local_inputs(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    local_players: Res<LocalPlayers>,
+    settings: Res<Settings>,
+) {
+    let mut local_inputs = HashMap::new();
+    let ball_target = settings.ball_counter.min(u8::MAX as u16) as u8;
+
+    for handle in &local_players.0 {
+        let mut buttons = 0u8;
+        if keyboard.pressed(KeyCode::KeyW) {
+            buttons |= input_bits::UP;
+        }
+        if keyboard.pressed(KeyCode::KeyS) {
+            buttons |= input_bits::DOWN;
+        }
+        if keyboard.pressed(KeyCode::KeyA) {
+            buttons |= input_bits::LEFT;
+        }
+        if keyboard.pressed(KeyCode::KeyD) {
+            buttons |= input_bits::RIGHT;
+        }
+        if keyboard.pressed(KeyCode::Space) {
+            buttons |= input_bits::JUMP;
+        }
+        if keyboard.pressed(KeyCode::ShiftLeft) {
+            buttons |= input_bits::SPRINT;
+        }
+        local_inputs.insert(*handle, BoxInput { buttons, ball_target });
+    }
+
+    commands.insert_resource(LocalInputs::<NetworkConfig>(local_inputs));
+}
+
+// Replaces the old direct-keyboard-read system, which would desync under prediction.
+fn apply_rollback_inputs(
+    inputs: Res<PlayerInputs<NetworkConfig>>,
+    settings: Res<Settings>,
+    mut query: Query<(&mut Transform, &CameraController), With<RotataCamera>>,
+) {
+    let Ok((mut transform, controller)) = query.single_mut() else {
+        return;
+    };
+    // Handle 0 drives this machine's view of the shared ball pit; handle 1's input
+    // (the remote player) is reserved for that player's own rig once it's spawned.
+    let (input, _status) = inputs[0];
+
+    // Horizontal basis from yaw alone, so looking up/down doesn't slow or speed up
+    // walking the way `transform.forward()` (which includes pitch) would.
+    let yaw_rotation = Quat::from_axis_angle(Vec3::Y, controller.yaw);
+    let forward = yaw_rotation * Vec3::NEG_Z;
+    let right = yaw_rotation * Vec3::X;
+
+    let mut direction = Vec3::ZERO;
+    if input.buttons & input_bits::UP != 0 {
+        direction += forward;
+    }
+    if input.buttons & input_bits::DOWN != 0 {
+        direction -= forward;
+    }
+    if input.buttons & input_bits::LEFT != 0 {
+        direction -= right;
+    }
+    if input.buttons & input_bits::RIGHT != 0 {
+        direction += right;
+    }
+
+    if direction.length_squared() > 0.0 {
+        direction = direction.normalize();
+        let mut speed = settings.move_speed;
+        if input.buttons & input_bits::SPRINT != 0 {
+            speed *= SPRINT_MULTIPLIER;
+        }
+        transform.translation += direction * (speed / FPS as f32);
+    }
 }
 
 fn stay_inside_big_ball_system(
-    mut small_ball_query: Query<(&mut Transform, &Collider, &mut Velocity), With<SmallBall>>,
+    mut small_ball_query: Query<(&mut Transform, &phys::Collider, &mut phys::Velocity), (With<SmallBall>, Without<BigBall>)>,
+    big_ball_query: Query<(&Transform, &phys::Collider), With<BigBall>>,
 ) {
-    // Hard-coded value from your setup function
-    let big_ball_radius = 14.0;
-    // This should match the exact position where you spawn the big sphere in setup()
-    let big_ball_center = Vec3::new(0.0, 14.0 + 5.0, 0.0); // sphere_size + 5.0
-    
+    // Read the cavity's live radius/position from the tagged entity instead of the
+    // constants `setup` spawned it with, so this tracks the sphere if it ever moves.
+    let Ok((big_ball_transform, big_ball_collider)) = big_ball_query.single() else {
+        return;
+    };
+    let Some(big_ball_radius) = phys::ball_radius(big_ball_collider) else {
+        return;
+    };
+    let big_ball_center = big_ball_transform.translation;
+
     for (mut transform, collider, mut velocity) in small_ball_query.iter_mut() {
-        if let Some(ball) = collider.as_ball() {
-            let small_ball_radius = ball.radius();
-            
+        if let Some(small_ball_radius) = phys::ball_radius(collider) {
             // Vector from big sphere center to small ball center
             let to_small_ball = transform.translation - big_ball_center;
             let distance = to_small_ball.length();
-            
+
             // The maximum allowed distance is slightly reduced to ensure the ball stays visibly inside
             let max_distance = big_ball_radius - small_ball_radius - 0.2; // Added a small buffer
-            
+
             if distance > max_distance {
                 // Normalize direction vector
                 let dir = to_small_ball.normalize();
-                
+
                 // Reposition the ball to be inside
                 transform.translation = big_ball_center + dir * max_distance;
-                
+
                 // Apply the bounce by reflecting the velocity vector
                 // Calculate the normal at the point of collision (pointing inward)
                 let normal = -dir;
-                
+
                 // Only bounce if the ball is moving outward
-                let dot_product = velocity.linvel.dot(normal);
+                let linvel = phys::linear_velocity(&velocity);
+                let dot_product = linvel.dot(normal);
                 if dot_product < 0.0 {
                     // Standard reflection formula: v_new = v_old - 2(v_old·n)n
-                    velocity.linvel = velocity.linvel - 2.0 * dot_product * normal;
+                    phys::set_linear_velocity(&mut velocity, linvel - 2.0 * dot_product * normal);
                     println!("Ball bounced at distance: {}", distance);
                 }
             }
@@ -126,34 +455,6 @@ fn stay_inside_big_ball_system(
     }
 }
 
-fn mouse_movement(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    timer: Res<Time>,
-    mut query: Query<&mut Transform, With<RotataCamera>>,
-) {
-    let mut transform = query.single_mut().unwrap();
-    let mut direction = Vec3::ZERO;
-
-    if keyboard.pressed(KeyCode::KeyW) {
-        direction += transform.forward().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyS) {
-        direction += transform.back().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyA) {
-        direction += transform.left().as_vec3();
-    }
-    if keyboard.pressed(KeyCode::KeyD) {
-        direction += transform.right().as_vec3();
-    }
-
-    if direction.length_squared() > 0.0 {
-        direction = direction.normalize();
-        let speed = 5.0;
-        transform.translation += direction * speed * timer.delta_secs();
-    }
-}
-
 fn move_up(
     timer: Res<Time>,
     mut query: Query<&mut Transform, With<RotataCamera>>
@@ -170,9 +471,22 @@ fn move_up(
     }
 }
 
+// ~88 degrees, just shy of straight up/down so the look direction never flips through the poles.
+const MAX_PITCH: f32 = 1.54;
+
+// Yaw/pitch kept separate (not composed onto `Transform::rotation` directly) so roll
+// can't creep in and pitch can't spin past vertical. Rollback-tracked (like `Transform`
+// and `Velocity`) since `apply_rollback_inputs` reads it inside `GgrsSchedule` —
+// otherwise a resimulated frame would use today's yaw/pitch instead of the frame's own.
+#[derive(Component, Default, Clone, Copy)]
+struct CameraController {
+    yaw: f32,
+    pitch: f32,
+}
+
 fn mouse_free_look(
-    mut cam: Query<&mut Transform, With<RotataCamera>>,
-    timer: Res<Time>,
+    mut cam: Query<(&mut Transform, &mut CameraController), With<RotataCamera>>,
+    settings: Res<Settings>,
     mut evr_mouse_motion: EventReader<MouseMotion>,
     mut windows: Query<&mut Window, With<PrimaryWindow>>,
 ) {
@@ -181,66 +495,92 @@ fn mouse_free_look(
     if window.cursor_options.visible == false
         // && window.cursor_options.grab_mode == CursorGrabMode::Confined
     {
-        let mouse_sensitivity = MOUSE_SENSITIVITY.lock().unwrap();
-        let mut transform = cam.single_mut().unwrap();
+        let mouse_sensitivity = settings.mouse_sensitivity;
+        let Ok((mut transform, mut controller)) = cam.single_mut() else {
+            return;
+        };
 
         for event in evr_mouse_motion.read() {
-            let delta = event.delta * *mouse_sensitivity * timer.delta_secs();
-
-            let yaw = Quat::from_rotation_y(-delta.x);
-            let pitch = Quat::from_rotation_x(-delta.y);
-
-            transform.rotation = yaw * transform.rotation.normalize();
-            transform.rotation = transform.rotation.normalize() * pitch;
+            controller.yaw -= event.delta.x * mouse_sensitivity;
+            controller.pitch -= event.delta.y * mouse_sensitivity;
         }
+        controller.pitch = controller.pitch.clamp(-MAX_PITCH, MAX_PITCH);
+
+        transform.rotation =
+            Quat::from_axis_angle(Vec3::Y, controller.yaw) * Quat::from_axis_angle(Vec3::X, controller.pitch);
     }
 }
 
-fn game_ui(mut contexts: EguiContexts) {
-    let mut vsync_status = VSYNC.lock().unwrap();
-    let mut ball_counter = BALL_COUNTER.lock().unwrap();
-    let mut mouse_sensitivity = MOUSE_SENSITIVITY.lock().unwrap();
-    let mut ground_size = GROUND_SIZE.lock().unwrap();
+fn skybox_label(path: &str) -> &str {
+    path.trim_start_matches("textures/").trim_end_matches(".png")
+}
 
-    
+fn game_ui(
+    mut contexts: EguiContexts,
+    asset_server: Res<AssetServer>,
+    mut skybox: ResMut<SkyboxState>,
+    mut settings: ResMut<Settings>,
+) {
     let min_ball: u16 = 0;
     let max_ball: u16 = 100;
 
     let min_sensi: f32 = 0.1;
     let max_sensi: f32 = 1.0;
 
+    let min_speed: f32 = 1.0;
+    let max_speed: f32 = 20.0;
+
     egui::Window::new("Settings")
         .resizable(true)
         .show(contexts.ctx_mut(), |ui| {
-            ui.checkbox(&mut *vsync_status, "Vsync");
+            ui.checkbox(&mut settings.vsync, "Vsync");
 
             ui.add(egui::Label::new("Ball Counter"));
-            ui.add(egui::Slider::new(&mut *ball_counter, min_ball..=max_ball));
+            ui.add(egui::Slider::new(&mut settings.ball_counter, min_ball..=max_ball));
 
             ui.add(egui::Label::new("Mouse Sensitivity"));
             ui.add(egui::Slider::new(
-                &mut *mouse_sensitivity,
+                &mut settings.mouse_sensitivity,
                 min_sensi..=max_sensi,
             ));
+
+            ui.add(egui::Label::new("Move Speed"));
+            ui.add(egui::Slider::new(&mut settings.move_speed, min_speed..=max_speed));
+
+            ui.add(egui::Label::new("Skybox"));
+            egui::ComboBox::from_id_salt("skybox_select")
+                .selected_text(skybox_label(SKYBOXES[skybox.index]))
+                .show_ui(ui, |ui| {
+                    for (index, path) in SKYBOXES.iter().enumerate() {
+                        if ui
+                            .selectable_label(skybox.index == index, skybox_label(path))
+                            .clicked()
+                            && skybox.index != index
+                        {
+                            skybox.index = index;
+                            skybox.handle = asset_server.load(*path);
+                            skybox.reinterpreted = false;
+                        }
+                    }
+                });
         });
 
     egui::Window::new("Ground Size")
         .resizable(true)
         .show(contexts.ctx_mut(), |ui| {
             ui.add(egui::Label::new("X-Axis"));
-            ui.add(egui::Slider::new(&mut ground_size.x, 10.0..=100.0));
+            ui.add(egui::Slider::new(&mut settings.ground_size.x, 10.0..=100.0));
             ui.add(egui::Label::new("Y-Axis"));
-            ui.add(egui::Slider::new(&mut ground_size.y, 0.5..=2.0));
+            ui.add(egui::Slider::new(&mut settings.ground_size.y, 0.5..=2.0));
             ui.add(egui::Label::new("Z-Axis"));
-            ui.add(egui::Slider::new(&mut ground_size.z, 10.0..=100.0));
+            ui.add(egui::Slider::new(&mut settings.ground_size.z, 10.0..=100.0));
         });
 }
 
-fn game_setting(mut windows: Query<&mut Window, With<PrimaryWindow>>) {
+fn game_setting(mut windows: Query<&mut Window, With<PrimaryWindow>>, settings: Res<Settings>) {
     let mut window = windows.single_mut().unwrap();
-    let vsync_status = VSYNC.lock().unwrap();
 
-    if *vsync_status == true {
+    if settings.vsync {
         window.present_mode = PresentMode::AutoVsync;
     } else {
         window.present_mode = PresentMode::AutoNoVsync;
@@ -276,11 +616,58 @@ fn lock_hide_cursor(mut windows: Query<&mut Window, With<PrimaryWindow>>) {
 }
 
 fn setup_camera(mut commands: Commands) {
-    commands.spawn((
-        RotataCamera,
-        Camera3d::default(),
-        Transform::from_xyz(-1.0, 10.0, 30.0).looking_at(Vec3::ZERO, Vec3::Y),
-    ));
+    let transform = Transform::from_xyz(-1.0, 10.0, 30.0).looking_at(Vec3::ZERO, Vec3::Y);
+    // `mouse_free_look` rebuilds `transform.rotation` from `CameraController` the moment
+    // the mouse moves, so seed yaw/pitch from this starting orientation instead of
+    // `default()` — otherwise the first mouse event would snap the camera back to
+    // facing -Z.
+    let (yaw, pitch, _roll) = transform.rotation.to_euler(EulerRot::YXZ);
+
+    commands.spawn((RotataCamera, CameraController { yaw, pitch }, Camera3d::default(), transform));
+}
+
+fn setup_skybox(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SkyboxState {
+        handle: asset_server.load(SKYBOXES[0]),
+        index: 0,
+        reinterpreted: false,
+    });
+}
+
+// Reinterprets the loaded cubemap as a 6-layer cube array once and attaches `Skybox`
+// to the camera. Swapping `SkyboxState::handle` clears `reinterpreted` to redo it.
+fn apply_skybox(
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut skybox: ResMut<SkyboxState>,
+    mut commands: Commands,
+    camera: Query<Entity, With<RotataCamera>>,
+) {
+    if skybox.reinterpreted {
+        return;
+    }
+    if asset_server.load_state(&skybox.handle) != LoadState::Loaded {
+        return;
+    }
+
+    let image = images.get_mut(&skybox.handle).expect("skybox image finished loading");
+    if image.texture_descriptor.array_layer_count() == 1 {
+        image.reinterpret_stacked_2d_as_array(6);
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..default()
+        });
+    }
+
+    if let Ok(camera_entity) = camera.single() {
+        commands.entity(camera_entity).insert(Skybox {
+            image: skybox.handle.clone(),
+            brightness: 1000.0,
+            ..default()
+        });
+    }
+
+    skybox.reinterpreted = true;
 }
 
 fn setup_light(mut commands: Commands) {
@@ -297,13 +684,13 @@ fn spawn_ground(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
+    ground_size: Vec3,
 ) {
-    let ground_size = GROUND_SIZE.lock().unwrap();
     commands.spawn((
         Ground,
         Mesh3d(meshes.add(Cuboid::new(ground_size.x, ground_size.y, ground_size.z))),
         MeshMaterial3d(materials.add(Color::WHITE)),
-        Collider::cuboid(
+        phys::cuboid_collider(
             ground_size.x / 2.0,
             ground_size.y / 2.0,
             ground_size.z / 2.0,
@@ -317,24 +704,99 @@ fn ground_change_detector(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut prev: ResMut<PreviousGroundSize>,
+    settings: Res<Settings>,
     query: Query<Entity, With<Ground>>,
 ) {
-    let current = *GROUND_SIZE.lock().unwrap();
+    let current = settings.ground_size;
 
     if current != prev.0 {
         for entity in query.iter() {
             commands.entity(entity).despawn();
         }
 
-        spawn_ground(&mut commands, &mut meshes, &mut materials);
+        spawn_ground(&mut commands, &mut meshes, &mut materials, current);
         prev.0 = current;
     }
 }
 
+// Seeded deterministically (not `rand::thread_rng()`) so both peers spawn identical
+// balls; `seed` comes from `sync_ball_count`'s target/index.
+fn spawn_small_ball(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    big_ball_center: Vec3,
+    seed: u64,
+) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    // Jittered per ball so a slider jump (e.g. 0 to 50) doesn't spawn a stack of
+    // fully-overlapping colliders for the solver to violently eject apart.
+    let offset = Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(0.0..1.0), rng.gen_range(-1.0..1.0));
+    let spawn_point = big_ball_center + Vec3::new(0.0, -2.0, 0.0) + offset;
+    let velocity = phys::velocity(Vec3::new(
+        rng.gen_range(-2.0..2.0),
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(-2.0..2.0),
+    ));
+
+    commands
+        .spawn((
+            SmallBall,
+            phys::ball_collider(0.5),
+            phys::dynamic_body(),
+            phys::restitution(1.0),
+            Transform::from_translation(spawn_point),
+            Mesh3d(meshes.add(Sphere::new(0.5))),
+            MeshMaterial3d(materials.add(Color::srgb_u8(124, 144, 255))),
+            phys::ExternalImpulse::default(),
+            velocity,
+            phys::ccd_enabled(),
+        ))
+        .add_rollback();
+}
+
+// Recomputed fresh every tick, so there's no rollback state to save here.
+fn sync_ball_target(inputs: Res<PlayerInputs<NetworkConfig>>, mut target: ResMut<BallTarget>) {
+    // Handle 0 owns the ball-pit target for now; see `apply_rollback_inputs` for the
+    // same one-sided convention.
+    let (input, _status) = inputs[0];
+    target.0 = input.ball_target as u16;
+}
+
+fn sync_ball_count(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    target: Res<BallTarget>,
+    small_balls: Query<Entity, With<SmallBall>>,
+    big_ball: Query<&Transform, With<BigBall>>,
+) {
+    let Ok(big_ball_transform) = big_ball.single() else {
+        return;
+    };
+    let target = target.0 as usize;
+    let current = small_balls.iter().count();
+
+    if current < target {
+        for index in current..target {
+            // Seeded from the target count and spawn index, not the frame number, so
+            // the same ball gets the same starting velocity however many times
+            // rollback resimulates the tick that spawns it.
+            let seed = (target as u64) * 1000 + index as u64;
+            spawn_small_ball(&mut commands, &mut meshes, &mut materials, big_ball_transform.translation, seed);
+        }
+    } else if current > target {
+        for entity in small_balls.iter().take(current - target) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 fn setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<Settings>,
 ) {
     let sphere_size = 14.0;
     // FPS Counter
@@ -347,13 +809,14 @@ fn setup(
 
     // Ground
     commands.insert_resource(PreviousGroundSize(Vec3::ZERO));
-    spawn_ground(&mut commands, &mut meshes, &mut materials);
+    spawn_ground(&mut commands, &mut meshes, &mut materials, settings.ground_size);
 
     // Transparent Sphere Collider
     commands.spawn((
-        Collider::ball(sphere_size),
-        ColliderMassProperties::Mass(0.0),
-        RigidBody::Fixed,
+        BigBall,
+        phys::ball_collider(sphere_size),
+        phys::massless(),
+        phys::fixed_body(),
         Mesh3d(meshes.add(Sphere::new(sphere_size))),
         MeshMaterial3d(materials.add(StandardMaterial {
             base_color: Color::Srgba(Srgba {red: 24.0, green: 176.0, blue: 162.0, alpha: 0.1 }),
@@ -363,20 +826,6 @@ fn setup(
         Transform::from_xyz(0.0, sphere_size + 5.0, 0.0),
     ));
 
-    // Sphere
-    // let ball_counter = *BALL_COUNTER.lock().unwrap();
-    commands.spawn((
-        SmallBall,
-        Collider::ball(0.5),
-        RigidBody::Dynamic,
-        Restitution::coefficient(1.0),
-        Transform::from_xyz(0.0, 12.0, 0.0),
-        Mesh3d(meshes.add(Sphere::new(0.5))),
-        MeshMaterial3d(materials.add(Color::srgb_u8(124, 144, 255))),
-        ExternalImpulse::default(),
-        Velocity::default(),
-        Ccd {
-            enabled: true
-        }
-    ));  
+    // Balls are spawned by `sync_ball_count` once `BigBall` exists, to match the Ball
+    // Counter slider instead of hard-coding a single starting marble here.
 }
@@ -0,0 +1,137 @@
+//! Backend-agnostic physics layer.
+//!
+//! The rest of the crate spawns bodies and reads/writes velocities through the
+//! constructors and accessors re-exported here instead of naming `bevy_rapier3d` or
+//! `avian3d` directly, so the `avian` feature can swap the backend without touching
+//! `main.rs`'s spawn/query code. Full GGRS rollback scheduling (`PhysicsSet` wiring in
+//! `GgrsSchedule`) stays Rapier-only for now — see `main`'s `build_physics` — since
+//! Avian doesn't yet have the same rollback integration.
+//!
+//! NOTE: this snapshot has no `Cargo.toml`, so there's nowhere to declare the `avian`
+//! feature or an optional `avian3d` dependency yet. The `#[cfg(feature = "avian")]`
+//! branch below is unreachable until that manifest wiring exists.
+
+use bevy::prelude::*;
+
+#[cfg(not(feature = "avian"))]
+mod backend {
+    use bevy::prelude::{Bundle, Vec3};
+
+    pub use bevy_rapier3d::prelude::{Ccd, Collider, ExternalImpulse, NoUserData, Restitution, RigidBody, Velocity};
+    pub use bevy_rapier3d::prelude::RapierPhysicsPlugin;
+    #[cfg(debug_assertions)]
+    pub use bevy_rapier3d::prelude::RapierDebugRenderPlugin as DebugRenderPlugin;
+
+    pub fn ball_collider(radius: f32) -> Collider {
+        Collider::ball(radius)
+    }
+
+    pub fn cuboid_collider(half_x: f32, half_y: f32, half_z: f32) -> Collider {
+        Collider::cuboid(half_x, half_y, half_z)
+    }
+
+    pub fn ball_radius(collider: &Collider) -> Option<f32> {
+        collider.as_ball().map(|ball| ball.radius())
+    }
+
+    pub fn fixed_body() -> RigidBody {
+        RigidBody::Fixed
+    }
+
+    pub fn dynamic_body() -> RigidBody {
+        RigidBody::Dynamic
+    }
+
+    pub fn velocity(linvel: Vec3) -> Velocity {
+        Velocity { linvel, angvel: Vec3::ZERO }
+    }
+
+    pub fn linear_velocity(velocity: &Velocity) -> Vec3 {
+        velocity.linvel
+    }
+
+    pub fn set_linear_velocity(velocity: &mut Velocity, linvel: Vec3) {
+        velocity.linvel = linvel;
+    }
+
+    pub fn restitution(coefficient: f32) -> Restitution {
+        Restitution::coefficient(coefficient)
+    }
+
+    pub fn ccd_enabled() -> Ccd {
+        Ccd { enabled: true }
+    }
+
+    /// Zeroes out the mass contribution of a fixed collider (e.g. the transparent
+    /// cavity sphere) so it never drags down dynamic bodies resting against it.
+    pub fn massless() -> impl Bundle {
+        bevy_rapier3d::prelude::ColliderMassProperties::Mass(0.0)
+    }
+
+    pub fn physics_plugin() -> RapierPhysicsPlugin<NoUserData> {
+        RapierPhysicsPlugin::<NoUserData>::default().with_default_system_setup(false)
+    }
+}
+
+#[cfg(feature = "avian")]
+mod backend {
+    use avian3d::math::Scalar;
+    use bevy::prelude::Vec3;
+
+    pub use avian3d::prelude::{
+        Collider, ExternalImpulse, LinearVelocity as Velocity, PhysicsDebugPlugin as DebugRenderPlugin,
+        PhysicsPlugins, Restitution, RigidBody, SweptCcd as Ccd,
+    };
+
+    pub fn ball_collider(radius: Scalar) -> Collider {
+        Collider::sphere(radius)
+    }
+
+    pub fn cuboid_collider(half_x: Scalar, half_y: Scalar, half_z: Scalar) -> Collider {
+        Collider::cuboid(half_x * 2.0, half_y * 2.0, half_z * 2.0)
+    }
+
+    pub fn ball_radius(collider: &Collider) -> Option<f32> {
+        collider.shape().as_ball().map(|ball| ball.radius)
+    }
+
+    pub fn fixed_body() -> RigidBody {
+        RigidBody::Static
+    }
+
+    pub fn dynamic_body() -> RigidBody {
+        RigidBody::Dynamic
+    }
+
+    pub fn velocity(linvel: Vec3) -> Velocity {
+        Velocity(linvel)
+    }
+
+    pub fn linear_velocity(velocity: &Velocity) -> Vec3 {
+        velocity.0
+    }
+
+    pub fn set_linear_velocity(velocity: &mut Velocity, linvel: Vec3) {
+        velocity.0 = linvel;
+    }
+
+    pub fn restitution(coefficient: f32) -> Restitution {
+        Restitution::new(coefficient)
+    }
+
+    pub fn ccd_enabled() -> Ccd {
+        Ccd::default()
+    }
+
+    /// Avian bodies with `RigidBody::Static` never contribute mass to begin with, so
+    /// there's no Rapier-style `ColliderMassProperties` override needed here.
+    pub fn massless() -> impl bevy::prelude::Bundle {
+        ()
+    }
+
+    pub fn physics_plugin() -> PhysicsPlugins {
+        PhysicsPlugins::default()
+    }
+}
+
+pub use backend::*;